@@ -2,6 +2,9 @@ use wasm_bindgen::prelude::*;
 use rand::Rng;
 use std::collections::HashSet;
 
+mod map_builder;
+use map_builder::{CellularAutomataBuilder, MapBuilder, RoomsBuilder};
+
 const MAP_WIDTH: usize = 60;
 const MAP_HEIGHT: usize = 20;
 const FOV_RADIUS: i32 = 8;
@@ -100,40 +103,28 @@ impl Game {
         game
     }
     
+    // Odd floors use the original room scatter, even floors get
+    // cellular-automata caves.
+    fn select_builder(&self) -> Box<dyn MapBuilder> {
+        if self.floor % 2 == 0 {
+            Box::new(CellularAutomataBuilder)
+        } else {
+            Box::new(RoomsBuilder)
+        }
+    }
+
     fn generate_map(&mut self) {
         let mut rng = rand::thread_rng();
-        
-        // Simple room generation
-        for _ in 0..10 {
-            let room_w = rng.gen_range(4..10);
-            let room_h = rng.gen_range(4..8);
-            let room_x = rng.gen_range(1..MAP_WIDTH - room_w - 1);
-            let room_y = rng.gen_range(1..MAP_HEIGHT - room_h - 1);
-            
-            for y in room_y..room_y + room_h {
-                for x in room_x..room_x + room_w {
-                    self.map[y][x] = TileType::Floor;
-                }
-            }
-        }
-        
-        // Connect rooms with corridors
-        for y in 1..MAP_HEIGHT - 1 {
-            for x in 1..MAP_WIDTH - 1 {
-                if rng.gen_bool(0.1) {
-                    self.map[y][x] = TileType::Floor;
-                }
-            }
-        }
-        
+        let builder = self.select_builder();
+        let (map, (px, py), _stairs_pos) =
+            builder.build(MAP_WIDTH, MAP_HEIGHT, self.floor, &mut rng);
+        self.map = map;
+
         // Place player
-        let px = rng.gen_range(5..MAP_WIDTH - 5) as i32;
-        let py = rng.gen_range(5..MAP_HEIGHT - 5) as i32;
-        self.map[py as usize][px as usize] = TileType::Floor;
         let player = Entity::new(EntityType::Player, px, py);
         self.entities.push(player);
         self.player_index = 0;
-        
+
         // Place monsters and items
         for _ in 0..10 {
             let x = rng.gen_range(1..MAP_WIDTH - 1) as i32;
@@ -149,11 +140,6 @@ impl Game {
                 self.entities.push(Entity::new(entity_type, x, y));
             }
         }
-        
-        // Place stairs
-        let sx = rng.gen_range(1..MAP_WIDTH - 1);
-        let sy = rng.gen_range(1..MAP_HEIGHT - 1);
-        self.map[sy][sx] = TileType::StairsDown;
     }
     
     fn is_blocked(&self, x: i32, y: i32) -> bool {
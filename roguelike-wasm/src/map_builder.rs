@@ -0,0 +1,228 @@
+use rand::{Rng, RngCore};
+use std::collections::{HashSet, VecDeque};
+
+use crate::TileType;
+
+// Tile grid, player start, and stairs position produced by a MapBuilder.
+pub type BuildResult = (Vec<Vec<TileType>>, (i32, i32), (usize, usize));
+
+pub trait MapBuilder {
+    fn build(&self, width: usize, height: usize, floor: i32, rng: &mut dyn RngCore) -> BuildResult;
+}
+
+// The original generator: a scatter of rectangular rooms plus a sprinkling
+// of random floor tiles to loosely connect them.
+pub struct RoomsBuilder;
+
+impl MapBuilder for RoomsBuilder {
+    fn build(&self, width: usize, height: usize, _floor: i32, rng: &mut dyn RngCore) -> BuildResult {
+        let mut map = vec![vec![TileType::Wall; width]; height];
+
+        for _ in 0..10 {
+            let room_w = rng.gen_range(4..10);
+            let room_h = rng.gen_range(4..8);
+            let room_x = rng.gen_range(1..width - room_w - 1);
+            let room_y = rng.gen_range(1..height - room_h - 1);
+
+            for row in map.iter_mut().skip(room_y).take(room_h) {
+                for tile in row.iter_mut().skip(room_x).take(room_w) {
+                    *tile = TileType::Floor;
+                }
+            }
+        }
+
+        for row in map.iter_mut().skip(1).take(height - 2) {
+            for tile in row.iter_mut().skip(1).take(width - 2) {
+                if rng.gen_bool(0.1) {
+                    *tile = TileType::Floor;
+                }
+            }
+        }
+
+        let px = rng.gen_range(5..width - 5) as i32;
+        let py = rng.gen_range(5..height - 5) as i32;
+        map[py as usize][px as usize] = TileType::Floor;
+
+        let sx = rng.gen_range(1..width - 1);
+        let sy = rng.gen_range(1..height - 1);
+        map[sy][sx] = TileType::StairsDown;
+
+        (map, (px, py), (sx, sy))
+    }
+}
+
+// Cave generator: smooth random noise with a cellular automaton, then keep
+// only the largest connected region so the player and stairs are always
+// reachable from each other.
+pub struct CellularAutomataBuilder;
+
+impl CellularAutomataBuilder {
+    const FLOOR_CHANCE: f64 = 0.55;
+    const SMOOTHING_ITERATIONS: u32 = 5;
+    const WALL_THRESHOLD: usize = 5;
+    const MAX_GENERATION_ATTEMPTS: u32 = 5;
+
+    fn wall_neighbors(map: &[Vec<TileType>], x: usize, y: usize) -> usize {
+        let mut count = 0;
+        for dy in -1..=1i32 {
+            for dx in -1..=1i32 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let nx = x as i32 + dx;
+                let ny = y as i32 + dy;
+                let out_of_bounds =
+                    nx < 0 || ny < 0 || ny as usize >= map.len() || nx as usize >= map[0].len();
+                if out_of_bounds || map[ny as usize][nx as usize] == TileType::Wall {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    fn smooth(map: &[Vec<TileType>], width: usize, height: usize) -> Vec<Vec<TileType>> {
+        let mut next = map.to_vec();
+        for (y, row) in next.iter_mut().enumerate().skip(1).take(height - 2) {
+            for (x, tile) in row.iter_mut().enumerate().skip(1).take(width - 2) {
+                *tile = if Self::wall_neighbors(map, x, y) >= Self::WALL_THRESHOLD {
+                    TileType::Wall
+                } else {
+                    TileType::Floor
+                };
+            }
+        }
+        next
+    }
+
+    // Flood-fills every floor region and returns the largest one.
+    fn largest_floor_region(map: &[Vec<TileType>], width: usize, height: usize) -> Vec<(usize, usize)> {
+        let mut visited = HashSet::new();
+        let mut largest = Vec::new();
+
+        for (y, row) in map.iter().enumerate() {
+            for (x, &tile) in row.iter().enumerate() {
+                if tile != TileType::Floor || visited.contains(&(x, y)) {
+                    continue;
+                }
+
+                let mut region = Vec::new();
+                let mut queue = VecDeque::new();
+                queue.push_back((x, y));
+                visited.insert((x, y));
+
+                while let Some((cx, cy)) = queue.pop_front() {
+                    region.push((cx, cy));
+                    for (nx, ny) in [
+                        (cx.wrapping_sub(1), cy),
+                        (cx + 1, cy),
+                        (cx, cy.wrapping_sub(1)),
+                        (cx, cy + 1),
+                    ] {
+                        if nx < width
+                            && ny < height
+                            && map[ny][nx] == TileType::Floor
+                            && !visited.contains(&(nx, ny))
+                        {
+                            visited.insert((nx, ny));
+                            queue.push_back((nx, ny));
+                        }
+                    }
+                }
+
+                if region.len() > largest.len() {
+                    largest = region;
+                }
+            }
+        }
+
+        largest
+    }
+
+    // BFS from `start` to the farthest reachable tile within `region`, used
+    // to place the stairs as far from the player as possible.
+    fn farthest_tile(region: &HashSet<(usize, usize)>, start: (usize, usize)) -> (usize, usize) {
+        let mut visited = HashSet::new();
+        let mut queue = VecDeque::new();
+        queue.push_back(start);
+        visited.insert(start);
+        let mut farthest = start;
+
+        while let Some((cx, cy)) = queue.pop_front() {
+            farthest = (cx, cy);
+            for (nx, ny) in [
+                (cx.wrapping_sub(1), cy),
+                (cx + 1, cy),
+                (cx, cy.wrapping_sub(1)),
+                (cx, cy + 1),
+            ] {
+                if region.contains(&(nx, ny)) && !visited.contains(&(nx, ny)) {
+                    visited.insert((nx, ny));
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+
+        farthest
+    }
+
+    // Generates noise + smooths it until a non-empty floor region survives,
+    // retrying a bounded number of times. If every attempt collapses to solid
+    // wall, carves a single floor tile so callers always get somewhere to
+    // stand instead of panicking.
+    fn generate_region(
+        width: usize,
+        height: usize,
+        rng: &mut dyn RngCore,
+    ) -> (Vec<Vec<TileType>>, Vec<(usize, usize)>) {
+        for _ in 0..Self::MAX_GENERATION_ATTEMPTS {
+            let mut map = vec![vec![TileType::Wall; width]; height];
+
+            for row in map.iter_mut().skip(1).take(height - 2) {
+                for tile in row.iter_mut().skip(1).take(width - 2) {
+                    *tile = if rng.gen_bool(Self::FLOOR_CHANCE) {
+                        TileType::Floor
+                    } else {
+                        TileType::Wall
+                    };
+                }
+            }
+
+            for _ in 0..Self::SMOOTHING_ITERATIONS {
+                map = Self::smooth(&map, width, height);
+            }
+
+            let region = Self::largest_floor_region(&map, width, height);
+            if !region.is_empty() {
+                return (map, region);
+            }
+        }
+
+        let mut map = vec![vec![TileType::Wall; width]; height];
+        let cx = width / 2;
+        let cy = height / 2;
+        map[cy][cx] = TileType::Floor;
+        (map, vec![(cx, cy)])
+    }
+}
+
+impl MapBuilder for CellularAutomataBuilder {
+    fn build(&self, width: usize, height: usize, _floor: i32, rng: &mut dyn RngCore) -> BuildResult {
+        let (mut map, region) = Self::generate_region(width, height, rng);
+        let region_set: HashSet<(usize, usize)> = region.iter().cloned().collect();
+
+        for (y, row) in map.iter_mut().enumerate() {
+            for (x, tile) in row.iter_mut().enumerate() {
+                if *tile == TileType::Floor && !region_set.contains(&(x, y)) {
+                    *tile = TileType::Wall;
+                }
+            }
+        }
+
+        let player_start = region[0];
+        let stairs_pos = Self::farthest_tile(&region_set, player_start);
+        map[stairs_pos.1][stairs_pos.0] = TileType::StairsDown;
+
+        (map, (player_start.0 as i32, player_start.1 as i32), stairs_pos)
+    }
+}